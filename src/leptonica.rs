@@ -57,6 +57,10 @@ impl FileFormat {
     }
 }
 
+/// Error returned when a `Pix` could not be constructed from the given source.
+#[derive(Debug, PartialEq)]
+pub struct PixReadError {}
+
 impl Pix {
     // TODO: read from std::fs::File
     pub fn from_path(path: &std::path::Path) -> Result<Pix, ()> {
@@ -74,6 +78,61 @@ impl Pix {
         }
     }
 
+    /// Reads a `Pix` from encoded image bytes held in memory, detecting the
+    /// format like [`Pix::from_path`] does.
+    pub fn from_mem(data: &[u8]) -> Result<Pix, PixReadError> {
+        let pix =
+            unsafe { leptonica_sys::pixReadMem(data.as_ptr(), data.len()) };
+        if pix.is_null() {
+            Err(PixReadError {})
+        } else {
+            Ok(Pix { raw: pix })
+        }
+    }
+
+    /// Builds a 32-bpp `Pix` from a raw RGBA buffer laid out row by row, one
+    /// byte per channel.
+    pub fn from_raw_rgba(data: &[u8], width: u32, height: u32) -> Result<Pix, PixReadError> {
+        if data.len() != (width as usize) * (height as usize) * 4 {
+            return Err(PixReadError {});
+        }
+        let pix = unsafe { leptonica_sys::pixCreate(width as i32, height as i32, 32) };
+        if pix.is_null() {
+            return Err(PixReadError {});
+        }
+        for y in 0..height {
+            for x in 0..width {
+                let i = ((y * width + x) * 4) as usize;
+                let val = (u32::from(data[i]) << 24)
+                    | (u32::from(data[i + 1]) << 16)
+                    | (u32::from(data[i + 2]) << 8)
+                    | u32::from(data[i + 3]);
+                unsafe {
+                    leptonica_sys::pixSetPixel(pix, x as i32, y as i32, val);
+                }
+            }
+        }
+        Ok(Pix { raw: pix })
+    }
+
+    /// Encodes the image into `format` and returns the bytes, without touching
+    /// the filesystem.
+    pub fn to_mem(&self, format: FileFormat) -> Result<Vec<u8>, ()> {
+        let mut data: *mut u8 = std::ptr::null_mut();
+        let mut size: usize = 0;
+        if unsafe {
+            leptonica_sys::pixWriteMem(&mut data, &mut size, self.raw, format.to_int())
+        } != 0
+        {
+            return Err(());
+        }
+        let re = unsafe { std::slice::from_raw_parts(data, size).to_vec() };
+        unsafe {
+            leptonica_sys::lept_free(data as *mut std::ffi::c_void);
+        }
+        Ok(re)
+    }
+
     pub fn clip(&self, rectangle: &Box) -> Self {
         Pix {
             raw: {
@@ -0,0 +1,100 @@
+//! Wrappers around Tesseract's `TessResultRenderer` for turning a sequence of
+//! images into a single searchable PDF or concatenated hOCR/text document.
+
+use super::capi;
+use super::tesseract;
+
+/// Error returned when a [`Renderer`] could not be created.
+#[derive(Debug, PartialEq)]
+pub enum RendererCreateError {
+    /// An argument contained an interior nul byte.
+    NulError(std::ffi::NulError),
+    /// Tesseract returned a NULL renderer (e.g. unwritable output or bad datapath).
+    CreationFailed,
+}
+
+impl From<std::ffi::NulError> for RendererCreateError {
+    fn from(e: std::ffi::NulError) -> RendererCreateError {
+        RendererCreateError::NulError(e)
+    }
+}
+
+/// A result renderer accumulating recognized pages into one output document.
+///
+/// Create one with [`Renderer::new_pdf`], [`Renderer::new_text`] or
+/// [`Renderer::new_hocr`], wrap the pages between [`Renderer::begin_document`]
+/// and [`Renderer::end_document`], and add each recognized image with
+/// [`Renderer::add_image`].
+pub struct Renderer {
+    pub(crate) raw: *mut capi::TessResultRenderer,
+}
+
+impl Drop for Renderer {
+    fn drop(&mut self) {
+        unsafe {
+            capi::TessResultRendererDelete(self.raw);
+        }
+    }
+}
+
+impl Renderer {
+    /// Creates a renderer writing a searchable PDF to `outputbase`.pdf.
+    ///
+    /// `datapath` must point at the tessdata directory; set `text_only` to omit
+    /// the background image from the PDF.
+    pub fn new_pdf(
+        outputbase: &str,
+        datapath: &str,
+        text_only: bool,
+    ) -> Result<Renderer, RendererCreateError> {
+        let outputbase = std::ffi::CString::new(outputbase)?;
+        let datapath = std::ffi::CString::new(datapath)?;
+        Renderer::from_raw(unsafe {
+            capi::TessPDFRendererCreate(
+                outputbase.as_ptr(),
+                datapath.as_ptr(),
+                if text_only { 1 } else { 0 },
+            )
+        })
+    }
+
+    /// Creates a renderer writing plain UTF-8 text to `outputbase`.txt.
+    pub fn new_text(outputbase: &str) -> Result<Renderer, RendererCreateError> {
+        let outputbase = std::ffi::CString::new(outputbase)?;
+        Renderer::from_raw(unsafe { capi::TessTextRendererCreate(outputbase.as_ptr()) })
+    }
+
+    /// Creates a renderer writing hOCR to `outputbase`.hocr.
+    pub fn new_hocr(outputbase: &str) -> Result<Renderer, RendererCreateError> {
+        let outputbase = std::ffi::CString::new(outputbase)?;
+        Renderer::from_raw(unsafe { capi::TessHOcrRendererCreate(outputbase.as_ptr()) })
+    }
+
+    fn from_raw(raw: *mut capi::TessResultRenderer) -> Result<Renderer, RendererCreateError> {
+        if raw.is_null() {
+            Err(RendererCreateError::CreationFailed)
+        } else {
+            Ok(Renderer { raw })
+        }
+    }
+
+    /// Begins a document with the given title. Returns `false` on failure.
+    pub fn begin_document(&self, title: &str) -> bool {
+        let title = match std::ffi::CString::new(title) {
+            Ok(title) => title,
+            Err(_) => return false,
+        };
+        unsafe { capi::TessResultRendererBeginDocument(self.raw, title.as_ptr()) == 1 }
+    }
+
+    /// Renders the image currently set on `api` as the next page. Returns
+    /// `false` on failure.
+    pub fn add_image(&self, api: &tesseract::TessBaseApiImageSet) -> bool {
+        unsafe { capi::TessResultRendererAddImage(self.raw, api.raw()) == 1 }
+    }
+
+    /// Ends the document, flushing it to disk. Returns `false` on failure.
+    pub fn end_document(&self) -> bool {
+        unsafe { capi::TessResultRendererEndDocument(self.raw) == 1 }
+    }
+}
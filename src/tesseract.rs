@@ -2,15 +2,109 @@
 
 use super::capi;
 use super::leptonica;
+use super::renderer;
+
+/// Page segmentation mode, mirroring Tesseract's `TessPageSegMode`.
+pub enum PageSegMode {
+    /// Orientation and script detection only.
+    OsdOnly,
+    /// Automatic page segmentation with orientation and script detection.
+    AutoOsd,
+    /// Automatic page segmentation, but no OSD, or OCR.
+    AutoOnly,
+    /// Fully automatic page segmentation, but no OSD.
+    Auto,
+    /// Assume a single column of text of variable sizes.
+    SingleColumn,
+    /// Assume a single uniform block of vertically aligned text.
+    SingleBlockVertText,
+    /// Assume a single uniform block of text.
+    SingleBlock,
+    /// Treat the image as a single text line.
+    SingleLine,
+    /// Treat the image as a single word.
+    SingleWord,
+    /// Treat the image as a single word in a circle.
+    CircleWord,
+    /// Treat the image as a single character.
+    SingleChar,
+    /// Find as much text as possible in no particular order.
+    SparseText,
+    /// Sparse text with orientation and script detection.
+    SparseTextOsd,
+    /// Treat the image as a single text line, bypassing hacks specific to Tesseract.
+    RawLine,
+}
+
+impl PageSegMode {
+    fn to_int(&self) -> capi::TessPageSegMode {
+        match self {
+            PageSegMode::OsdOnly => capi::TessPageSegMode_PSM_OSD_ONLY,
+            PageSegMode::AutoOsd => capi::TessPageSegMode_PSM_AUTO_OSD,
+            PageSegMode::AutoOnly => capi::TessPageSegMode_PSM_AUTO_ONLY,
+            PageSegMode::Auto => capi::TessPageSegMode_PSM_AUTO,
+            PageSegMode::SingleColumn => capi::TessPageSegMode_PSM_SINGLE_COLUMN,
+            PageSegMode::SingleBlockVertText => capi::TessPageSegMode_PSM_SINGLE_BLOCK_VERT_TEXT,
+            PageSegMode::SingleBlock => capi::TessPageSegMode_PSM_SINGLE_BLOCK,
+            PageSegMode::SingleLine => capi::TessPageSegMode_PSM_SINGLE_LINE,
+            PageSegMode::SingleWord => capi::TessPageSegMode_PSM_SINGLE_WORD,
+            PageSegMode::CircleWord => capi::TessPageSegMode_PSM_CIRCLE_WORD,
+            PageSegMode::SingleChar => capi::TessPageSegMode_PSM_SINGLE_CHAR,
+            PageSegMode::SparseText => capi::TessPageSegMode_PSM_SPARSE_TEXT,
+            PageSegMode::SparseTextOsd => capi::TessPageSegMode_PSM_SPARSE_TEXT_OSD,
+            PageSegMode::RawLine => capi::TessPageSegMode_PSM_RAW_LINE,
+        }
+    }
+}
+
+/// OCR engine mode, mirroring Tesseract's `TessOcrEngineMode`.
+pub enum OcrEngineMode {
+    /// Run the legacy Tesseract engine only.
+    TesseractOnly,
+    /// Run the LSTM neural-net engine only.
+    LstmOnly,
+    /// Run both engines and combine the results.
+    TesseractLstmCombined,
+    /// Let Tesseract choose based on what is available in the data.
+    Default,
+}
+
+impl OcrEngineMode {
+    fn to_int(&self) -> capi::TessOcrEngineMode {
+        match self {
+            OcrEngineMode::TesseractOnly => capi::TessOcrEngineMode_OEM_TESSERACT_ONLY,
+            OcrEngineMode::LstmOnly => capi::TessOcrEngineMode_OEM_LSTM_ONLY,
+            OcrEngineMode::TesseractLstmCombined => {
+                capi::TessOcrEngineMode_OEM_TESSERACT_LSTM_COMBINED
+            }
+            OcrEngineMode::Default => capi::TessOcrEngineMode_OEM_DEFAULT,
+        }
+    }
+}
 
 #[derive(Debug, PartialEq)]
-pub struct TessInitError {
-    pub code: i32,
+pub enum TessInitError {
+    /// Tesseract returned a non-zero code from its init function.
+    InitFailed(i32),
+    /// A supplied language or path contained an interior nul byte.
+    NulError(std::ffi::NulError),
+    /// A supplied path was not valid UTF-8.
+    InvalidUtf8,
 }
 
 impl std::fmt::Display for TessInitError {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "TessInitError{{{}}}", self.code)
+        match self {
+            TessInitError::InitFailed(code) => write!(f, "TessInitError{{{}}}", code),
+            TessInitError::NulError(e) => write!(f, "TessInitError{{{}}}", e),
+            TessInitError::InvalidUtf8 => write!(f, "TessInitError{{invalid utf-8 path}}"),
+        }
+    }
+}
+
+impl From<std::ffi::NulError> for TessInitError {
+    fn from(e: std::ffi::NulError) -> TessInitError {
+        TessInitError::NulError(e)
     }
 }
 
@@ -29,12 +123,10 @@ impl TessBaseApiUninitializedPointer {
         }
     }
 
-    // Return a result instead of panicking if -1 is reachable
-    fn init(&self, datapath: *const i8, language: *const i8) {
+    fn init(&self, datapath: *const i8, language: *const i8) -> Result<(), TessInitError> {
         match unsafe { capi::TessBaseAPIInit3(self.raw, datapath, language) } {
-            0 => (),
-            -1 => panic!("Failed to initialize"),
-            _ => unreachable!(),
+            0 => Ok(()),
+            code => Err(TessInitError::InitFailed(code)),
         }
     }
 }
@@ -75,44 +167,56 @@ impl TessBaseApiUnitialized {
         }
     }
 
-    pub fn init(self) -> TessBaseApiInitialized {
-        self.pointer.init(std::ptr::null(), std::ptr::null());
-        self.create_tess_base_api_initialized()
+    pub fn init(self) -> Result<TessBaseApiInitialized, TessInitError> {
+        self.pointer.init(std::ptr::null(), std::ptr::null())?;
+        Ok(self.create_tess_base_api_initialized())
     }
 
-    pub fn init_with_lang(self, language: &str) -> TessBaseApiInitialized {
-        self.pointer.init(
-            std::ptr::null(),
-            std::ffi::CString::new(language).unwrap().as_ptr(),
-        );
-        self.create_tess_base_api_initialized()
+    pub fn init_with_lang(self, language: &str) -> Result<TessBaseApiInitialized, TessInitError> {
+        let language = std::ffi::CString::new(language)?;
+        self.pointer.init(std::ptr::null(), language.as_ptr())?;
+        Ok(self.create_tess_base_api_initialized())
     }
 
-    pub fn init_with_datapath(self, datapath: &std::path::Path) -> TessBaseApiInitialized {
-        unsafe {
-            capi::TessBaseAPIInit3(
-                self.pointer.raw,
-                std::ffi::CString::new(datapath.to_str().unwrap())
-                    .unwrap()
-                    .as_ptr(),
-                std::ptr::null(),
-            );
-        }
-        self.create_tess_base_api_initialized()
+    pub fn init_with_datapath(
+        self,
+        datapath: &std::path::Path,
+    ) -> Result<TessBaseApiInitialized, TessInitError> {
+        let datapath =
+            std::ffi::CString::new(datapath.to_str().ok_or(TessInitError::InvalidUtf8)?)?;
+        self.pointer.init(datapath.as_ptr(), std::ptr::null())?;
+        Ok(self.create_tess_base_api_initialized())
     }
 
     pub fn init_with_datapath_and_lang(
         self,
         datapath: &std::path::Path,
         language: &str,
-    ) -> TessBaseApiInitialized {
-        self.pointer.init(
-            std::ffi::CString::new(datapath.to_str().unwrap())
-                .unwrap()
-                .as_ptr(),
-            std::ffi::CString::new(language).unwrap().as_ptr(),
-        );
-        self.create_tess_base_api_initialized()
+    ) -> Result<TessBaseApiInitialized, TessInitError> {
+        let datapath =
+            std::ffi::CString::new(datapath.to_str().ok_or(TessInitError::InvalidUtf8)?)?;
+        let language = std::ffi::CString::new(language)?;
+        self.pointer.init(datapath.as_ptr(), language.as_ptr())?;
+        Ok(self.create_tess_base_api_initialized())
+    }
+
+    pub fn init_with_oem(
+        self,
+        language: &str,
+        oem: OcrEngineMode,
+    ) -> Result<TessBaseApiInitialized, TessInitError> {
+        let language = std::ffi::CString::new(language)?;
+        match unsafe {
+            capi::TessBaseAPIInit2(
+                self.pointer.raw,
+                std::ptr::null(),
+                language.as_ptr(),
+                oem.to_int(),
+            )
+        } {
+            0 => Ok(self.create_tess_base_api_initialized()),
+            code => Err(TessInitError::InitFailed(code)),
+        }
     }
 
     fn create_tess_base_api_initialized(self) -> TessBaseApiInitialized {
@@ -127,6 +231,67 @@ impl TessBaseApiUnitialized {
 }
 
 impl TessBaseApiInitialized {
+    pub(crate) fn raw(&self) -> *mut capi::TessBaseAPI {
+        self.pointer.raw
+    }
+
+    /// Renders a file of one or more images into `renderer`, producing e.g. a
+    /// searchable PDF. Returns `false` on failure.
+    pub fn process_pages(&self, filename: &std::path::Path, renderer: &renderer::Renderer) -> bool {
+        let filename = match filename.to_str().and_then(|f| std::ffi::CString::new(f).ok()) {
+            Some(filename) => filename,
+            None => return false,
+        };
+        unsafe {
+            capi::TessBaseAPIProcessPages(
+                self.pointer.raw,
+                filename.as_ptr(),
+                std::ptr::null(),
+                0,
+                renderer.raw,
+            ) == 1
+        }
+    }
+
+    /// Renders a single in-memory [`leptonica::Pix`] page into `renderer`.
+    /// Returns `false` on failure.
+    pub fn process_page(
+        &self,
+        pix: &leptonica::Pix,
+        page_index: i32,
+        filename: &str,
+        renderer: &renderer::Renderer,
+    ) -> bool {
+        let filename = match std::ffi::CString::new(filename) {
+            Ok(filename) => filename,
+            Err(_) => return false,
+        };
+        unsafe {
+            capi::TessBaseAPIProcessPage(
+                self.pointer.raw,
+                pix.raw,
+                page_index,
+                filename.as_ptr(),
+                std::ptr::null(),
+                0,
+                renderer.raw,
+            ) == 1
+        }
+    }
+
+    /// Sets the page segmentation mode used for the next recognition.
+    pub fn set_page_seg_mode(&self, mode: PageSegMode) {
+        unsafe { capi::TessBaseAPISetPageSegMode(self.pointer.raw, mode.to_int()) }
+    }
+
+    /// Sets a Tesseract config variable (e.g. `tessedit_char_whitelist`).
+    ///
+    /// Returns `false` if `name` is not a known variable, or if either
+    /// argument contains an interior nul byte.
+    pub fn set_variable(&self, name: &str, value: &str) -> bool {
+        set_variable(self.pointer.raw, name, value)
+    }
+
     /// Drops self and returns TessBaseApiImageSet signifying an image has been given
     pub fn set_image(self, img: &leptonica::Pix) -> TessBaseApiImageSet {
         unsafe { capi::TessBaseAPISetImage2(self.pointer.raw, img.raw) }
@@ -141,6 +306,23 @@ impl TessBaseApiInitialized {
 }
 
 impl TessBaseApiImageSet {
+    pub(crate) fn raw(&self) -> *mut capi::TessBaseAPI {
+        self.pointer.raw
+    }
+
+    /// Sets the page segmentation mode used for the next recognition.
+    pub fn set_page_seg_mode(&self, mode: PageSegMode) {
+        unsafe { capi::TessBaseAPISetPageSegMode(self.pointer.raw, mode.to_int()) }
+    }
+
+    /// Sets a Tesseract config variable (e.g. `tessedit_char_whitelist`).
+    ///
+    /// Returns `false` if `name` is not a known variable, or if either
+    /// argument contains an interior nul byte.
+    pub fn set_variable(&self, name: &str, value: &str) -> bool {
+        set_variable(self.pointer.raw, name, value)
+    }
+
     pub fn set_rectangle(&self, rectangle: &leptonica::Box) {
         unsafe {
             capi::TessBaseAPISetRectangle(
@@ -162,6 +344,33 @@ impl TessBaseApiImageSet {
         }
     }
 
+    pub fn get_hocr(&self, page: i32) -> String {
+        unsafe {
+            let sptr = capi::TessBaseAPIGetHOCRText(self.pointer.raw, page);
+            let re = std::ffi::CStr::from_ptr(sptr).to_str().unwrap().to_string();
+            capi::TessDeleteText(sptr);
+            return re;
+        }
+    }
+
+    pub fn get_alto(&self, page: i32) -> String {
+        unsafe {
+            let sptr = capi::TessBaseAPIGetAltoText(self.pointer.raw, page);
+            let re = std::ffi::CStr::from_ptr(sptr).to_str().unwrap().to_string();
+            capi::TessDeleteText(sptr);
+            return re;
+        }
+    }
+
+    pub fn get_tsv(&self, page: i32) -> String {
+        unsafe {
+            let sptr = capi::TessBaseAPIGetTSVText(self.pointer.raw, page);
+            let re = std::ffi::CStr::from_ptr(sptr).to_str().unwrap().to_string();
+            capi::TessDeleteText(sptr);
+            return re;
+        }
+    }
+
     // Not public cause maybe not so idiomatic
     fn get_component_images(&self, iterator_level: u32, text_only: bool) -> leptonica::Boxes {
         leptonica::Boxes {
@@ -196,4 +405,152 @@ impl TessBaseApiImageSet {
     pub fn get_symbols(&self, text_only: bool) -> leptonica::Boxes {
         self.get_component_images(capi::TessPageIteratorLevel_RIL_SYMBOL, text_only)
     }
+
+    /// Returns an iterator over the recognition result at the given
+    /// [`PageIteratorLevel`] (symbol, word, textline, para or block), yielding
+    /// the text, confidence and bounding box of each element.
+    pub fn get_iterator(&self, level: PageIteratorLevel) -> ResultIterator {
+        ResultIterator {
+            raw: unsafe { capi::TessBaseAPIGetIterator(self.pointer.raw) },
+            level: level.to_int(),
+            started: false,
+        }
+    }
+}
+
+/// The granularity at which a [`ResultIterator`] walks the page, mirroring
+/// Tesseract's `TessPageIteratorLevel`.
+pub enum PageIteratorLevel {
+    /// Block of text/image/separator line.
+    Block,
+    /// Paragraph within a block.
+    Para,
+    /// Line within a paragraph.
+    Textline,
+    /// Word within a textline.
+    Word,
+    /// Symbol/character within a word.
+    Symbol,
+}
+
+impl PageIteratorLevel {
+    fn to_int(&self) -> u32 {
+        match self {
+            PageIteratorLevel::Block => capi::TessPageIteratorLevel_RIL_BLOCK,
+            PageIteratorLevel::Para => capi::TessPageIteratorLevel_RIL_PARA,
+            PageIteratorLevel::Textline => capi::TessPageIteratorLevel_RIL_TEXTLINE,
+            PageIteratorLevel::Word => capi::TessPageIteratorLevel_RIL_WORD,
+            PageIteratorLevel::Symbol => capi::TessPageIteratorLevel_RIL_SYMBOL,
+        }
+    }
+}
+
+fn set_variable(raw: *mut capi::TessBaseAPI, name: &str, value: &str) -> bool {
+    let name = match std::ffi::CString::new(name) {
+        Ok(name) => name,
+        Err(_) => return false,
+    };
+    let value = match std::ffi::CString::new(value) {
+        Ok(value) => value,
+        Err(_) => return false,
+    };
+    unsafe { capi::TessBaseAPISetVariable(raw, name.as_ptr(), value.as_ptr()) == 1 }
+}
+
+/// The bounding box of a result element, in pixel coordinates of the image.
+#[derive(Debug, PartialEq)]
+pub struct BoundingBox {
+    pub left: i32,
+    pub top: i32,
+    pub right: i32,
+    pub bottom: i32,
+}
+
+/// A single element produced by [`ResultIterator`], carrying its recognized
+/// text, confidence (0-100) and bounding box.
+#[derive(Debug, PartialEq)]
+pub struct ResultItem {
+    pub text: String,
+    pub confidence: f32,
+    pub bounding_box: BoundingBox,
+}
+
+/// Iterator over the recognition result at a chosen `TessPageIteratorLevel`.
+///
+/// Built on `TessBaseAPIGetIterator`; the first call to [`Iterator::next`]
+/// reports the first element, and each later call advances the underlying
+/// `TessResultIterator` and reports the element it lands on.
+pub struct ResultIterator {
+    raw: *mut capi::TessResultIterator,
+    level: u32,
+    started: bool,
+}
+
+impl Drop for ResultIterator {
+    fn drop(&mut self) {
+        unsafe {
+            capi::TessResultIteratorDelete(self.raw);
+        }
+    }
+}
+
+impl ResultIterator {
+    fn current(&self) -> ResultItem {
+        unsafe {
+            let page = capi::TessResultIteratorGetPageIterator(self.raw);
+            let (mut left, mut top, mut right, mut bottom) = (0, 0, 0, 0);
+            capi::TessPageIteratorBoundingBox(
+                page,
+                self.level,
+                &mut left,
+                &mut top,
+                &mut right,
+                &mut bottom,
+            );
+
+            // Tesseract returns NULL for non-text/image regions and empty
+            // elements, so guard before building the CStr.
+            let sptr = capi::TessResultIteratorGetUTF8Text(self.raw, self.level);
+            let text = if sptr.is_null() {
+                String::new()
+            } else {
+                let text = std::ffi::CStr::from_ptr(sptr).to_str().unwrap().to_string();
+                capi::TessDeleteText(sptr);
+                text
+            };
+
+            ResultItem {
+                text,
+                confidence: capi::TessResultIteratorConfidence(self.raw, self.level),
+                bounding_box: BoundingBox {
+                    left,
+                    top,
+                    right,
+                    bottom,
+                },
+            }
+        }
+    }
+}
+
+impl Iterator for ResultIterator {
+    type Item = ResultItem;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // TessBaseAPIGetIterator returns NULL when recognition hasn't run.
+        if self.raw.is_null() {
+            return None;
+        }
+
+        if self.started {
+            let page = unsafe { capi::TessResultIteratorGetPageIterator(self.raw) };
+            if unsafe { capi::TessPageIteratorNext(page, self.level) } == 0 {
+                return None;
+            }
+        } else {
+            self.started = true;
+        }
+
+        Some(self.current())
+    }
 }